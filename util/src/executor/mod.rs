@@ -0,0 +1,352 @@
+//! A thread-pool executor for driving completion futures to completion on a set of worker threads.
+//!
+//! This module is only available with the `executor` feature enabled.
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use std::thread::{self, JoinHandle as ThreadHandle};
+
+use completion_core::CompletionFuture;
+
+use crate::future::block_on::{wake_pair, Parker};
+
+/// A boxed completion future suitable for spawning onto a [`ThreadPool`].
+pub type FutureObj = Pin<Box<dyn CompletionFuture<Output = ()> + Send>>;
+
+/// A thread pool for driving completion futures concurrently.
+///
+/// Each worker thread runs its own [`block_on`](crate::future::block_on)-style park loop over a
+/// shared queue of ready tasks. When a task's waker fires it is re-enqueued onto the pool rather
+/// than unparking a fixed thread.
+///
+/// On drop, the workers run the tasks already queued and then exit; the dropping thread then
+/// drives every task that has not yet finished to completion, honoring the crate's
+/// poll-to-completion contract.
+#[derive(Debug)]
+pub struct ThreadPool {
+    inner: Arc<PoolInner>,
+    workers: Vec<ThreadHandle<()>>,
+}
+
+#[derive(Debug)]
+struct PoolInner {
+    state: Mutex<PoolState>,
+}
+
+#[derive(Debug)]
+struct PoolState {
+    queue: VecDeque<Arc<Task>>,
+    /// Every task that has not yet finished, so that shutdown can drive each to completion.
+    all: Vec<Arc<Task>>,
+    /// Wakers of workers currently parked waiting for work.
+    idle: Vec<Waker>,
+    shutdown: bool,
+}
+
+impl ThreadPool {
+    /// Create a new thread pool with one worker per available CPU.
+    pub fn new() -> Self {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        Self::with_threads(threads)
+    }
+
+    /// Create a new thread pool with the given number of worker threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is zero.
+    pub fn with_threads(threads: usize) -> Self {
+        assert!(threads > 0, "a `ThreadPool` needs at least one worker thread");
+
+        let inner = Arc::new(PoolInner {
+            state: Mutex::new(PoolState {
+                queue: VecDeque::new(),
+                all: Vec::new(),
+                idle: Vec::new(),
+                shutdown: false,
+            }),
+        });
+
+        let workers = (0..threads)
+            .map(|_| {
+                let inner = Arc::clone(&inner);
+                thread::spawn(move || worker_loop(&inner))
+            })
+            .collect();
+
+        Self { inner, workers }
+    }
+
+    /// Spawn a completion future onto the pool, returning a handle that resolves to its output.
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: CompletionFuture + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let shared = Arc::new(JoinShared {
+            state: Mutex::new(JoinState::Pending(None)),
+        });
+        // Wrap once so that the future's completion notifies the handle.
+        let store = Store {
+            future,
+            shared: Arc::clone(&shared),
+        };
+        self.submit(Box::pin(store));
+        JoinHandle { shared }
+    }
+
+    /// Spawn a type-erased completion future onto the pool.
+    pub fn spawn_obj(&self, future: FutureObj) -> JoinHandle<()> {
+        let shared = Arc::new(JoinShared {
+            state: Mutex::new(JoinState::Pending(None)),
+        });
+        // A `FutureObj` already has unit output; wrap it so its completion notifies the handle.
+        let store = Store {
+            future,
+            shared: Arc::clone(&shared),
+        };
+        self.submit(Box::pin(store));
+        JoinHandle { shared }
+    }
+
+    /// Register an already-wrapped task future and enqueue it for execution.
+    fn submit(&self, future: FutureObj) {
+        let task = Arc::new(Task {
+            future: Mutex::new(Some(future)),
+            pool: Arc::downgrade(&self.inner),
+        });
+        {
+            let mut state = self.inner.state.lock().unwrap();
+            assert!(
+                !state.shutdown,
+                "cannot spawn onto a `ThreadPool` that is shutting down"
+            );
+            state.all.push(Arc::clone(&task));
+        }
+        self.inner.enqueue(task);
+    }
+}
+
+impl Default for ThreadPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        // Signal shutdown and wake any parked workers so they can exit.
+        let remaining = {
+            let mut state = self.inner.state.lock().unwrap();
+            state.shutdown = true;
+            for waker in state.idle.drain(..) {
+                waker.wake();
+            }
+            // Take ownership of every not-yet-finished task; now that shutdown is set, the workers
+            // exit once the queue is empty.
+            std::mem::take(&mut state.all)
+        };
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+
+        // Drive every in-flight task to completion rather than dropping it mid-poll.
+        let (parker, waker) = wake_pair();
+        let mut cx = Context::from_waker(&waker);
+        for task in remaining {
+            let mut slot = task.future.lock().unwrap();
+            if let Some(mut future) = slot.take() {
+                while unsafe { future.as_mut().poll(&mut cx) }.is_pending() {
+                    parker.park();
+                }
+            }
+        }
+    }
+}
+
+impl PoolInner {
+    fn enqueue(self: &Arc<Self>, task: Arc<Task>) {
+        let mut state = self.state.lock().unwrap();
+        state.queue.push_back(task);
+        if let Some(waker) = state.idle.pop() {
+            drop(state);
+            waker.wake();
+        }
+    }
+}
+
+fn worker_loop(inner: &Arc<PoolInner>) {
+    let (parker, waker) = wake_pair();
+    loop {
+        let task = {
+            let mut state = inner.state.lock().unwrap();
+            loop {
+                if let Some(task) = state.queue.pop_front() {
+                    break Some(task);
+                }
+                if state.shutdown {
+                    break None;
+                }
+                state.idle.push(waker.clone());
+                // Release the lock and park until a task is enqueued for us.
+                drop(state);
+                parker.park();
+                state = inner.state.lock().unwrap();
+            }
+        };
+
+        match task {
+            Some(task) => task.run(),
+            None => break,
+        }
+    }
+}
+
+struct Task {
+    future: Mutex<Option<FutureObj>>,
+    pool: Weak<PoolInner>,
+}
+
+impl std::fmt::Debug for Task {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Task").finish_non_exhaustive()
+    }
+}
+
+impl Task {
+    fn run(self: Arc<Self>) {
+        let mut slot = self.future.lock().unwrap();
+        let mut future = match slot.take() {
+            Some(future) => future,
+            None => return,
+        };
+
+        let waker = unsafe { Waker::from_raw(task_raw_waker(Arc::clone(&self))) };
+        let mut cx = Context::from_waker(&waker);
+        match unsafe { future.as_mut().poll(&mut cx) } {
+            Poll::Ready(()) => {
+                drop(slot);
+                // The task is finished; drop it from the pool's live set.
+                if let Some(pool) = self.pool.upgrade() {
+                    let mut state = pool.state.lock().unwrap();
+                    state.all.retain(|t| !Arc::ptr_eq(t, &self));
+                }
+            }
+            Poll::Pending => *slot = Some(future),
+        }
+    }
+}
+
+fn task_raw_waker(task: Arc<Task>) -> RawWaker {
+    RawWaker::new(Arc::into_raw(task) as *const (), &TASK_VTABLE)
+}
+
+unsafe fn task_clone(ptr: *const ()) -> RawWaker {
+    let task = Arc::from_raw(ptr as *const Task);
+    let cloned = Arc::clone(&task);
+    std::mem::forget(task);
+    task_raw_waker(cloned)
+}
+unsafe fn task_wake(ptr: *const ()) {
+    let task = Arc::from_raw(ptr as *const Task);
+    if let Some(pool) = task.pool.upgrade() {
+        pool.enqueue(task);
+    }
+}
+unsafe fn task_wake_by_ref(ptr: *const ()) {
+    let task = Arc::from_raw(ptr as *const Task);
+    if let Some(pool) = task.pool.upgrade() {
+        pool.enqueue(Arc::clone(&task));
+    }
+    std::mem::forget(task);
+}
+unsafe fn task_drop(ptr: *const ()) {
+    Arc::from_raw(ptr as *const Task);
+}
+
+const TASK_VTABLE: RawWakerVTable =
+    RawWakerVTable::new(task_clone, task_wake, task_wake_by_ref, task_drop);
+
+/// A handle to a task spawned on a [`ThreadPool`], resolving to that task's output.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you use them"]
+pub struct JoinHandle<T> {
+    shared: Arc<JoinShared<T>>,
+}
+
+#[derive(Debug)]
+struct JoinShared<T> {
+    state: Mutex<JoinState<T>>,
+}
+
+#[derive(Debug)]
+enum JoinState<T> {
+    /// Not yet finished; holds the waker of a task awaiting the handle.
+    Pending(Option<Waker>),
+    Ready(T),
+    Taken,
+}
+
+impl<T> CompletionFuture for JoinHandle<T> {
+    type Output = T;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.shared.state.lock().unwrap();
+        match std::mem::replace(&mut *state, JoinState::Taken) {
+            JoinState::Ready(output) => Poll::Ready(output),
+            JoinState::Pending(_) => {
+                *state = JoinState::Pending(Some(cx.waker().clone()));
+                Poll::Pending
+            }
+            JoinState::Taken => panic!("`JoinHandle` polled after completion"),
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// Adapter that stores a task's output into its [`JoinShared`] slot on completion.
+    struct Store<F, T> {
+        #[pin]
+        future: F,
+        shared: Arc<JoinShared<T>>,
+    }
+}
+
+impl<F: CompletionFuture<Output = T>, T> CompletionFuture for Store<F, T> {
+    type Output = ();
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        let output = match this.future.poll(cx) {
+            Poll::Ready(output) => output,
+            Poll::Pending => return Poll::Pending,
+        };
+        let mut state = this.shared.state.lock().unwrap();
+        let waker = match std::mem::replace(&mut *state, JoinState::Ready(output)) {
+            JoinState::Pending(waker) => waker,
+            _ => None,
+        };
+        drop(state);
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        Poll::Ready(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::future::block_on;
+    use crate::MustComplete;
+
+    #[test]
+    fn spawn_resolves_output() {
+        let pool = ThreadPool::with_threads(2);
+        let handle = pool.spawn(MustComplete::new(core::future::ready(42)));
+        assert_eq!(block_on(handle), 42);
+    }
+}
@@ -0,0 +1,202 @@
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll};
+
+use alloc::sync::Arc;
+
+use atomic_waker::AtomicWaker;
+use completion_core::CompletionFuture;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// A completion future that can be aborted through an [`AbortHandle`].
+    ///
+    /// This is created by the [`abortable`](crate::CompletionFutureExt::abortable) method, or by
+    /// [`Abortable::new`].
+    ///
+    /// Unlike [`futures_util::future::Abortable`], aborting does not drop the inner future mid-poll
+    /// — that would violate this crate's rule that a [`CompletionFuture`], once polled, must be
+    /// polled to completion. Instead [`AbortHandle::abort`] records the request and wakes the
+    /// task. The inner future is not signalled: on each subsequent poll it is still polled as
+    /// normal until it completes, and only once it reports readiness does `Abortable` resolve to
+    /// [`Poll::Ready`]`(`[`Err`]`(`[`Aborted`]`))`, discarding the output.
+    ///
+    /// [`futures_util::future::Abortable`]: https://docs.rs/futures-util/latest/futures_util/future/struct.Abortable.html
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct Abortable<Fut> {
+        #[pin]
+        inner: Fut,
+        reg: AbortRegistration,
+    }
+}
+
+impl<Fut> Abortable<Fut> {
+    /// Create a new `Abortable` future using the provided registration.
+    ///
+    /// Use [`AbortHandle::new_pair`] to create both the handle and the registration.
+    pub fn new(future: Fut, reg: AbortRegistration) -> Self {
+        Self { inner: future, reg }
+    }
+
+    /// Whether the associated [`AbortHandle`] has requested that this future be aborted.
+    ///
+    /// Note that a `true` result does not mean the future has finished unwinding; the inner future
+    /// is still driven to completion before `Abortable` resolves.
+    pub fn is_aborted(&self) -> bool {
+        self.reg.inner.aborted.load(Ordering::Relaxed)
+    }
+}
+
+/// A handle to an [`Abortable`] future, allowing it to be aborted.
+#[derive(Debug, Clone)]
+pub struct AbortHandle {
+    inner: Arc<AbortInner>,
+}
+
+impl AbortHandle {
+    /// Create an `AbortHandle` together with an [`AbortRegistration`] to pass to
+    /// [`Abortable::new`].
+    pub fn new_pair() -> (Self, AbortRegistration) {
+        let inner = Arc::new(AbortInner {
+            waker: AtomicWaker::new(),
+            aborted: AtomicBool::new(false),
+        });
+        (
+            Self {
+                inner: Arc::clone(&inner),
+            },
+            AbortRegistration { inner },
+        )
+    }
+
+    /// Request that the associated [`Abortable`] future be aborted.
+    ///
+    /// This wakes the task driving the future. The inner future is not told about the request; it
+    /// keeps being polled until it completes, and its output is then replaced by [`Aborted`].
+    pub fn abort(&self) {
+        self.inner.aborted.store(true, Ordering::Relaxed);
+        self.inner.waker.wake();
+    }
+
+    /// Whether [`abort`](Self::abort) has been called on this handle.
+    pub fn is_aborted(&self) -> bool {
+        self.inner.aborted.load(Ordering::Relaxed)
+    }
+}
+
+/// A registration handle for an [`Abortable`] future, created by [`AbortHandle::new_pair`].
+#[derive(Debug)]
+pub struct AbortRegistration {
+    inner: Arc<AbortInner>,
+}
+
+#[derive(Debug)]
+struct AbortInner {
+    waker: AtomicWaker,
+    aborted: AtomicBool,
+}
+
+/// Indicator that an [`Abortable`] future was aborted before it could complete.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Aborted;
+
+impl fmt::Display for Aborted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("`Abortable` future has been aborted")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Aborted {}
+
+impl<Fut: CompletionFuture> CompletionFuture for Abortable<Fut> {
+    type Output = Result<Fut::Output, Aborted>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        // Store our waker so that `AbortHandle::abort` can wake us even while the inner future is
+        // parked.
+        this.reg.inner.waker.register(cx.waker());
+
+        // We must keep driving the inner future to completion regardless of the abort flag; only
+        // its readiness lets us resolve.
+        let output = match this.inner.poll(cx) {
+            Poll::Ready(output) => output,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        Poll::Ready(if this.reg.inner.aborted.load(Ordering::Relaxed) {
+            Err(Aborted)
+        } else {
+            Ok(output)
+        })
+    }
+}
+
+impl<Fut: CompletionFuture + Future> Future for Abortable<Fut> {
+    type Output = Result<<Fut as Future>::Output, Aborted>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        this.reg.inner.waker.register(cx.waker());
+
+        // Disambiguate: with `Fut: CompletionFuture + Future` both `poll` methods apply.
+        let output = match Future::poll(this.inner, cx) {
+            Poll::Ready(output) => output,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        Poll::Ready(if this.reg.inner.aborted.load(Ordering::Relaxed) {
+            Err(Aborted)
+        } else {
+            Ok(output)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::future::block_on;
+
+    /// A completion future that returns `Pending` (waking itself) on its first poll and `Ready`
+    /// afterwards.
+    struct PendOnce {
+        polled: bool,
+    }
+    impl CompletionFuture for PendOnce {
+        type Output = i32;
+        unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<i32> {
+            let this = self.get_unchecked_mut();
+            if this.polled {
+                Poll::Ready(7)
+            } else {
+                this.polled = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn ok_when_not_aborted() {
+        let (_handle, reg) = AbortHandle::new_pair();
+        assert_eq!(block_on(Abortable::new(PendOnce { polled: false }, reg)), Ok(7));
+    }
+
+    #[test]
+    fn err_only_after_inner_ready() {
+        let (handle, reg) = AbortHandle::new_pair();
+        let abortable = Abortable::new(PendOnce { polled: false }, reg);
+        // Abort before the first poll; the future must still be driven to readiness before we see
+        // `Err(Aborted)`.
+        handle.abort();
+        assert!(abortable.is_aborted());
+        assert_eq!(block_on(abortable), Err(Aborted));
+    }
+}
@@ -51,7 +51,7 @@ pub fn block_on<F: CompletionFuture>(mut future: F) -> F::Output {
     })
 }
 
-fn wake_pair() -> (Parker, Waker) {
+pub(crate) fn wake_pair() -> (Parker, Waker) {
     let inner = Arc::new(WakerInner {
         woken: AtomicBool::new(false),
         sleeping_thread: thread::current(),
@@ -65,13 +65,13 @@ fn wake_pair() -> (Parker, Waker) {
     )
 }
 
-struct Parker {
+pub(crate) struct Parker {
     inner: Arc<WakerInner>,
     not_send_or_sync: PhantomData<*mut ()>,
 }
 
 impl Parker {
-    fn park(&self) {
+    pub(crate) fn park(&self) {
         while !self.inner.woken.swap(false, Ordering::SeqCst) {
             thread::park();
         }
@@ -0,0 +1,81 @@
+use core::any::Any;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::boxed::Box;
+use std::panic::{catch_unwind, AssertUnwindSafe, UnwindSafe};
+
+use completion_core::CompletionFuture;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// A completion future that catches panics from the inner future while it is being polled.
+    ///
+    /// This is created by the
+    /// [`catch_unwind`](crate::CompletionFutureExt::catch_unwind) method.
+    ///
+    /// Once a panic is caught the inner future is considered finished and is never polled again,
+    /// so the poll-to-completion contract is upheld without re-entering a future that has already
+    /// unwound.
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct CatchUnwind<F> {
+        #[pin]
+        inner: F,
+        done: bool,
+    }
+}
+
+impl<F> CatchUnwind<F> {
+    pub(crate) fn new(inner: F) -> Self {
+        Self { inner, done: false }
+    }
+}
+
+impl<F: CompletionFuture + UnwindSafe> CompletionFuture for CatchUnwind<F> {
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        assert!(!*this.done, "`CatchUnwind` polled after a caught panic");
+
+        match catch_unwind(AssertUnwindSafe(|| unsafe { this.inner.poll(cx) })) {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => {
+                // Fuse the future so its cancellation path never re-polls the panicked future.
+                *this.done = true;
+                Poll::Ready(Err(payload))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::future::block_on;
+    use crate::MustComplete;
+
+    #[test]
+    fn passes_through_output() {
+        let caught = block_on(CatchUnwind::new(MustComplete::new(core::future::ready(3))));
+        assert_eq!(caught.unwrap(), 3);
+    }
+
+    struct Boom;
+    impl CompletionFuture for Boom {
+        type Output = ();
+        unsafe fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            panic!("boom");
+        }
+    }
+
+    #[test]
+    fn catches_panic() {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+        let caught = block_on(CatchUnwind::new(Boom));
+        std::panic::set_hook(previous);
+        assert!(caught.is_err());
+    }
+}
@@ -0,0 +1,100 @@
+//! Utilities for the [`CompletionFuture`] trait.
+
+use core::future::Future;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use core::pin::Pin;
+
+use completion_core::CompletionFuture;
+
+use crate::MustComplete;
+
+pub(crate) mod block_on;
+pub use block_on::block_on;
+
+mod abortable;
+pub use abortable::{AbortHandle, AbortRegistration, Abortable, Aborted};
+
+mod select;
+pub use select::{select, Either, Select};
+
+#[cfg(feature = "std")]
+mod catch_unwind;
+#[cfg(feature = "std")]
+pub use catch_unwind::CatchUnwind;
+
+#[cfg(feature = "std")]
+mod shared;
+#[cfg(feature = "std")]
+pub use shared::{Shared, WeakShared};
+
+/// A boxed [`CompletionFuture`] that is [`Send`].
+#[cfg(feature = "alloc")]
+pub type BoxCompletionFuture<'a, T> = Pin<Box<dyn CompletionFuture<Output = T> + Send + 'a>>;
+
+/// A boxed [`CompletionFuture`] that is not necessarily [`Send`].
+#[cfg(feature = "alloc")]
+pub type LocalBoxCompletionFuture<'a, T> = Pin<Box<dyn CompletionFuture<Output = T> + 'a>>;
+
+/// Extension trait for [`Future`]s, providing conversions into [`CompletionFuture`]s.
+pub trait FutureExt: Future {
+    /// Make sure that this future will complete, yielding a [`CompletionFuture`].
+    fn must_complete(self) -> MustComplete<Self>
+    where
+        Self: Sized,
+    {
+        MustComplete::new(self)
+    }
+}
+impl<F: Future + ?Sized> FutureExt for F {}
+
+/// Extension trait for [`CompletionFuture`]s, providing combinators.
+pub trait CompletionFutureExt: CompletionFuture {
+    /// Wrap this future so that it can be aborted through the given [`AbortRegistration`].
+    ///
+    /// See [`Abortable`] for how aborting interacts with the poll-to-completion contract.
+    fn abortable(self, reg: AbortRegistration) -> Abortable<Self>
+    where
+        Self: Sized,
+    {
+        Abortable::new(self, reg)
+    }
+
+    /// Wait for this future or `other` to complete, yielding the loser for continued driving.
+    ///
+    /// See [`select`] for the fairness and [`Unpin`] requirements.
+    fn select<B>(self, other: B) -> Select<Self, B>
+    where
+        Self: Sized + Unpin,
+        B: CompletionFuture + Unpin,
+    {
+        select(self, other)
+    }
+
+    /// Catch panics unwinding out of this future while it is being polled.
+    ///
+    /// See [`CatchUnwind`] for how a caught panic interacts with the poll-to-completion contract.
+    #[cfg(feature = "std")]
+    fn catch_unwind(self) -> CatchUnwind<Self>
+    where
+        Self: Sized + std::panic::UnwindSafe,
+    {
+        CatchUnwind::new(self)
+    }
+
+    /// Create a clonable future that can be awaited from multiple places.
+    ///
+    /// See [`Shared`] for how the inner future is still driven to completion even if every clone
+    /// is dropped first.
+    #[cfg(feature = "std")]
+    fn shared(self) -> Shared<Self>
+    where
+        Self: Sized + Send + 'static,
+        Self::Output: Clone + Send,
+    {
+        Shared::new(self)
+    }
+}
+impl<F: CompletionFuture + ?Sized> CompletionFutureExt for F {}
@@ -0,0 +1,209 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+use pin_project_lite::pin_project;
+
+/// A value of one of two types; when both are completion futures with the same output, the
+/// `Either` is itself a completion future.
+///
+/// [`select`] resolves to an `Either` pairing the completed future's output with the
+/// *still-pending* completion future, which the caller is obligated to poll to completion (or
+/// drive through cancellation).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Either<A, B> {
+    /// The first branch of the `Either`.
+    Left(A),
+    /// The second branch of the `Either`.
+    Right(B),
+}
+
+impl<A, B> Either<A, B> {
+    /// Return whether this is an [`Either::Left`].
+    pub fn is_left(&self) -> bool {
+        matches!(self, Self::Left(_))
+    }
+
+    /// Return whether this is an [`Either::Right`].
+    pub fn is_right(&self) -> bool {
+        matches!(self, Self::Right(_))
+    }
+
+    /// Convert the left side into an [`Option`], discarding a right value.
+    pub fn left(self) -> Option<A> {
+        match self {
+            Self::Left(a) => Some(a),
+            Self::Right(_) => None,
+        }
+    }
+
+    /// Convert the right side into an [`Option`], discarding a left value.
+    pub fn right(self) -> Option<B> {
+        match self {
+            Self::Left(_) => None,
+            Self::Right(b) => Some(b),
+        }
+    }
+}
+
+impl<A, B> Either<A, B>
+where
+    A: CompletionFuture,
+    B: CompletionFuture<Output = A::Output>,
+{
+    fn project_pin(self: Pin<&mut Self>) -> Either<Pin<&mut A>, Pin<&mut B>> {
+        // SAFETY: we never move the contents out of the pinned `Either`.
+        unsafe {
+            match self.get_unchecked_mut() {
+                Self::Left(a) => Either::Left(Pin::new_unchecked(a)),
+                Self::Right(b) => Either::Right(Pin::new_unchecked(b)),
+            }
+        }
+    }
+}
+
+impl<A, B> CompletionFuture for Either<A, B>
+where
+    A: CompletionFuture,
+    B: CompletionFuture<Output = A::Output>,
+{
+    type Output = A::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project_pin() {
+            Either::Left(a) => a.poll(cx),
+            Either::Right(b) => b.poll(cx),
+        }
+    }
+}
+
+impl<A, B> Future for Either<A, B>
+where
+    A: CompletionFuture + Future<Output = <A as CompletionFuture>::Output>,
+    B: CompletionFuture<Output = <A as CompletionFuture>::Output>
+        + Future<Output = <A as CompletionFuture>::Output>,
+{
+    type Output = <A as CompletionFuture>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project_pin() {
+            Either::Left(a) => Future::poll(a, cx),
+            Either::Right(b) => Future::poll(b, cx),
+        }
+    }
+}
+
+pin_project! {
+    /// Waits for one of two completion futures to complete, yielding the loser for continued
+    /// driving.
+    ///
+    /// This is created by the [`select`] function and the
+    /// [`select`](crate::CompletionFutureExt::select) method.
+    ///
+    /// Because both inputs are [`CompletionFuture`]s that must be polled to completion once
+    /// started, `Select` cannot discard the losing future. It instead resolves to
+    /// [`Either`]`<(A::Output, B), (B::Output, A)>`, handing back the still-pending future so the
+    /// caller can uphold the contract.
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct Select<A, B> {
+        a: Option<A>,
+        b: Option<B>,
+        poll_a_first: bool,
+    }
+}
+
+/// Wait for one of two completion futures to complete.
+///
+/// On completion the winner's output is paired with the loser — a [`CompletionFuture`] the caller
+/// is obligated to poll to completion or drive through cancellation. The two futures are polled in
+/// an unspecified but fair order, alternating which is polled first on each call.
+///
+/// Both futures are required to be [`Unpin`]: once the winner is found, the loser (which may
+/// already have been polled) is handed back by value. Dropping the returned loser is only sound if
+/// it was never polled or has a no-op cancellation path.
+pub fn select<A, B>(a: A, b: B) -> Select<A, B>
+where
+    A: CompletionFuture + Unpin,
+    B: CompletionFuture + Unpin,
+{
+    Select {
+        a: Some(a),
+        b: Some(b),
+        poll_a_first: false,
+    }
+}
+
+impl<A, B> CompletionFuture for Select<A, B>
+where
+    A: CompletionFuture + Unpin,
+    B: CompletionFuture + Unpin,
+{
+    #[allow(clippy::type_complexity)]
+    type Output = Either<(A::Output, B), (B::Output, A)>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Both futures are `Unpin`, so we can work with plain references.
+        let this = self.get_mut();
+        this.poll_a_first = !this.poll_a_first;
+
+        for poll_a in [this.poll_a_first, !this.poll_a_first] {
+            if poll_a {
+                if let Some(fut) = this.a.as_mut() {
+                    if let Poll::Ready(output) = Pin::new(fut).poll(cx) {
+                        this.a = None;
+                        let loser = this.b.take().expect("`Select` polled after completion");
+                        return Poll::Ready(Either::Left((output, loser)));
+                    }
+                }
+            } else if let Some(fut) = this.b.as_mut() {
+                if let Poll::Ready(output) = Pin::new(fut).poll(cx) {
+                    this.b = None;
+                    let loser = this.a.take().expect("`Select` polled after completion");
+                    return Poll::Ready(Either::Right((output, loser)));
+                }
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::future::block_on;
+    use crate::MustComplete;
+
+    struct PendOnce {
+        polled: bool,
+    }
+    impl CompletionFuture for PendOnce {
+        type Output = i32;
+        unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<i32> {
+            let this = self.get_unchecked_mut();
+            if this.polled {
+                Poll::Ready(99)
+            } else {
+                this.polled = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn winner_paired_with_loser() {
+        let a = MustComplete::new(core::future::ready(1));
+        let b = PendOnce { polled: false };
+        match block_on(select(a, b)) {
+            Either::Left((output, loser)) => {
+                assert_eq!(output, 1);
+                // The loser is still pending and must be drivable to completion.
+                assert_eq!(block_on(loser), 99);
+            }
+            Either::Right(_) => panic!("the eagerly-ready future should win"),
+        }
+    }
+}
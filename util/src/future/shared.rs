@@ -0,0 +1,334 @@
+use core::pin::Pin;
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use std::boxed::Box;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::vec::Vec;
+
+use completion_core::CompletionFuture;
+
+use super::block_on::wake_pair;
+
+/// A clonable completion future, awaitable from multiple places.
+///
+/// This is created by the [`shared`](crate::CompletionFutureExt::shared) method.
+///
+/// The inner future lives behind an [`Arc`]; the first clone to poll drives it, while the others
+/// register their wakers and each receives a clone of the output once it completes.
+///
+/// To honor the crate's poll-to-completion contract, a started inner future is always driven to
+/// completion — even if every clone is dropped first, a detached worker thread finishes it rather
+/// than dropping it mid-poll.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you use them"]
+pub struct Shared<F: CompletionFuture> {
+    inner: Option<Arc<Inner<F>>>,
+}
+
+/// A [`Weak`] reference to a [`Shared`], created by [`Shared::downgrade`].
+#[derive(Debug)]
+pub struct WeakShared<F: CompletionFuture> {
+    inner: Weak<Inner<F>>,
+}
+
+struct Inner<F: CompletionFuture> {
+    state: Mutex<State<F>>,
+    notifier: Arc<Notifier>,
+    /// Number of live [`Shared`] clones; once this hits zero a started future is driven to
+    /// completion by a detached thread.
+    live: AtomicUsize,
+    started: AtomicBool,
+    /// Captured at construction, where the `Send` bounds are available, so that [`Drop`] can
+    /// detach the driver without needing those bounds itself.
+    detach: fn(Arc<Inner<F>>),
+}
+
+impl<F: CompletionFuture> core::fmt::Debug for Inner<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Inner")
+            .field("state", &*self.state.lock().unwrap())
+            .field("live", &self.live.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+enum State<F: CompletionFuture> {
+    /// Idle; the future is available for a clone to take and drive.
+    Pending(Pin<Box<F>>),
+    /// A clone has taken the future out and is currently driving it.
+    Driving,
+    Complete(F::Output),
+    /// A detached thread has taken over driving the future.
+    Detached,
+}
+
+impl<F: CompletionFuture> core::fmt::Debug for State<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::Pending(_) => "Pending",
+            Self::Driving => "Driving",
+            Self::Complete(_) => "Complete",
+            Self::Detached => "Detached",
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct Notifier {
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl Notifier {
+    fn register(&self, waker: &Waker) {
+        let mut wakers = self.wakers.lock().unwrap();
+        if !wakers.iter().any(|w| w.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+    fn wake_all(&self) {
+        for waker in self.wakers.lock().unwrap().drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+impl<F: CompletionFuture> Shared<F> {
+    pub(crate) fn new(future: F) -> Self
+    where
+        F: Send + 'static,
+        F::Output: Clone + Send,
+    {
+        Self {
+            inner: Some(Arc::new(Inner {
+                state: Mutex::new(State::Pending(Box::pin(future))),
+                notifier: Arc::new(Notifier::default()),
+                live: AtomicUsize::new(1),
+                started: AtomicBool::new(false),
+                detach: detach::<F>,
+            })),
+        }
+    }
+
+    fn inner(&self) -> &Arc<Inner<F>> {
+        self.inner.as_ref().expect("`Shared` used after being taken")
+    }
+
+    /// If the future has already completed, return a clone of its output.
+    pub fn peek(&self) -> Option<F::Output>
+    where
+        F::Output: Clone,
+    {
+        match &*self.inner().state.lock().unwrap() {
+            State::Complete(output) => Some(output.clone()),
+            _ => None,
+        }
+    }
+
+    /// Create a [`WeakShared`] reference to this future.
+    pub fn downgrade(&self) -> WeakShared<F> {
+        WeakShared {
+            inner: Arc::downgrade(self.inner()),
+        }
+    }
+}
+
+impl<F: CompletionFuture> WeakShared<F> {
+    /// Attempt to upgrade this reference into a [`Shared`], if any clone or the detached driver
+    /// still holds the shared state.
+    pub fn upgrade(&self) -> Option<Shared<F>> {
+        let inner = self.inner.upgrade()?;
+        inner.live.fetch_add(1, Ordering::Relaxed);
+        Some(Shared { inner: Some(inner) })
+    }
+}
+
+impl<F: CompletionFuture> Clone for Shared<F> {
+    fn clone(&self) -> Self {
+        let inner = Arc::clone(self.inner());
+        inner.live.fetch_add(1, Ordering::Relaxed);
+        Self { inner: Some(inner) }
+    }
+}
+
+impl<F> CompletionFuture for Shared<F>
+where
+    F: CompletionFuture,
+    F::Output: Clone,
+{
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let inner = Arc::clone(self.get_unchecked_mut().inner());
+        inner.started.store(true, Ordering::Relaxed);
+
+        // Always register our waker first so we are woken whether we drive or wait for another
+        // clone (or the detached driver) to finish.
+        inner.notifier.register(cx.waker());
+
+        // Decide whether we drive this poll, taking the future out so the lock is not held while
+        // polling it. This avoids deadlocking a future that re-enters the same `Shared`.
+        let mut future = {
+            let mut state = inner.state.lock().unwrap();
+            match &mut *state {
+                State::Complete(output) => return Poll::Ready(output.clone()),
+                // Someone else is driving, or a detached driver owns the future: just wait.
+                State::Driving | State::Detached => return Poll::Pending,
+                State::Pending(_) => match core::mem::replace(&mut *state, State::Driving) {
+                    State::Pending(future) => future,
+                    _ => unreachable!(),
+                },
+            }
+        };
+
+        let waker = notifier_waker(Arc::clone(&inner.notifier));
+        let mut cx = Context::from_waker(&waker);
+        let poll = future.as_mut().poll(&mut cx);
+
+        let mut state = inner.state.lock().unwrap();
+        match poll {
+            Poll::Ready(output) => {
+                *state = State::Complete(output.clone());
+                drop(state);
+                inner.notifier.wake_all();
+                Poll::Ready(output)
+            }
+            Poll::Pending => {
+                *state = State::Pending(future);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<F: CompletionFuture> Drop for Shared<F> {
+    fn drop(&mut self) {
+        let inner = match self.inner.take() {
+            Some(inner) => inner,
+            None => return,
+        };
+        // If we are the last live clone and the future has been started but not finished, a
+        // detached thread must still drive it to completion.
+        if inner.live.fetch_sub(1, Ordering::AcqRel) == 1
+            && inner.started.load(Ordering::Relaxed)
+        {
+            let take_over = matches!(&*inner.state.lock().unwrap(), State::Pending(_));
+            if take_over {
+                (inner.detach)(Arc::clone(&inner));
+            }
+        }
+    }
+}
+
+/// Spawn a thread that drives the inner future to completion using a `block_on`-style park loop.
+fn detach<F: CompletionFuture>(inner: Arc<Inner<F>>)
+where
+    F: Send + 'static,
+    F::Output: Send,
+{
+    thread::spawn(move || {
+        let mut future = match std::mem::replace(&mut *inner.state.lock().unwrap(), State::Detached)
+        {
+            State::Pending(future) => future,
+            _ => return,
+        };
+
+        let (parker, waker) = wake_pair();
+        let mut cx = Context::from_waker(&waker);
+        let output = loop {
+            if let Poll::Ready(output) = unsafe { future.as_mut().poll(&mut cx) } {
+                break output;
+            }
+            parker.park();
+        };
+        *inner.state.lock().unwrap() = State::Complete(output);
+        // Wake any clones that were upgraded from a `WeakShared` while we were driving.
+        inner.notifier.wake_all();
+    });
+}
+
+// A waker that, when fired, wakes every clone waiting on the shared future.
+
+fn notifier_waker(notifier: Arc<Notifier>) -> Waker {
+    unsafe { Waker::from_raw(RawWaker::new(Arc::into_raw(notifier) as *const (), &VTABLE)) }
+}
+
+unsafe fn clone(ptr: *const ()) -> RawWaker {
+    let notifier = Arc::from_raw(ptr as *const Notifier);
+    let cloned = Arc::clone(&notifier);
+    core::mem::forget(notifier);
+    RawWaker::new(Arc::into_raw(cloned) as *const (), &VTABLE)
+}
+unsafe fn wake(ptr: *const ()) {
+    let notifier = Arc::from_raw(ptr as *const Notifier);
+    notifier.wake_all();
+}
+unsafe fn wake_by_ref(ptr: *const ()) {
+    let notifier = Arc::from_raw(ptr as *const Notifier);
+    notifier.wake_all();
+    core::mem::forget(notifier);
+}
+unsafe fn drop(ptr: *const ()) {
+    Arc::from_raw(ptr as *const Notifier);
+}
+
+const VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::future::block_on;
+    use crate::MustComplete;
+
+    #[test]
+    fn late_clone_gets_cloned_output() {
+        let shared = Shared::new(MustComplete::new(core::future::ready(5)));
+        let late = shared.clone();
+        // Drive the first clone to completion.
+        assert_eq!(block_on(shared), 5);
+        // A clone created before completion still observes the cached output.
+        assert_eq!(late.peek(), Some(5));
+        assert_eq!(block_on(late), 5);
+    }
+
+    /// A future that completes (recording the fact) only on its second poll.
+    struct PendOnce {
+        polled: bool,
+        done: Arc<AtomicBool>,
+    }
+    impl CompletionFuture for PendOnce {
+        type Output = ();
+        unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            let this = self.get_unchecked_mut();
+            if this.polled {
+                this.done.store(true, Ordering::SeqCst);
+                Poll::Ready(())
+            } else {
+                this.polled = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn finishes_after_all_clones_dropped() {
+        let done = Arc::new(AtomicBool::new(false));
+        let mut shared = Shared::new(PendOnce {
+            polled: false,
+            done: Arc::clone(&done),
+        });
+
+        // Poll once so the inner future is started but still pending.
+        let (_parker, waker) = wake_pair();
+        let mut cx = Context::from_waker(&waker);
+        assert!(unsafe { Pin::new(&mut shared).poll(&mut cx) }.is_pending());
+
+        // Dropping the last live clone must hand the started future to a detached driver that
+        // finishes it rather than dropping it mid-poll.
+        drop(shared);
+        while !done.load(Ordering::SeqCst) {
+            thread::yield_now();
+        }
+    }
+}
@@ -6,6 +6,7 @@
 //! - `alloc`: Enables features that require allocation, on by default.
 //! - `macro`: Enables the [`completion`], [`completion_async`] and [`completion_async_move`]
 //! macros.
+//! - `executor`: Enables the [`executor`] thread-pool, which requires `std`.
 #![cfg_attr(not(feature = "std"), no_std)]
 
 #[cfg(feature = "alloc")]
@@ -28,6 +29,12 @@ pub use future::{BoxCompletionFuture, CompletionFutureExt, FutureExt, LocalBoxCo
 pub mod stream;
 pub use stream::{BoxCompletionStream, CompletionStreamExt, LocalBoxCompletionStream, StreamExt};
 
+pub mod sink;
+pub use sink::{CompletionSink, CompletionSinkExt, MustSink};
+
+#[cfg(feature = "executor")]
+pub mod executor;
+
 #[cfg(feature = "macro")]
 mod macros;
 #[cfg(feature = "macro")]
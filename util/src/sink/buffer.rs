@@ -0,0 +1,96 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::collections::VecDeque;
+
+use pin_project_lite::pin_project;
+
+use super::CompletionSink;
+
+pin_project! {
+    /// Sink adapter that buffers up to a fixed number of items before sending them onwards.
+    ///
+    /// This is created by [`CompletionSinkExt::buffer`](super::CompletionSinkExt::buffer).
+    #[must_use = "sinks do nothing unless you use them"]
+    pub struct Buffer<Si, Item> {
+        #[pin]
+        sink: Si,
+        buf: VecDeque<Item>,
+        capacity: usize,
+    }
+}
+
+impl<Si, Item> Buffer<Si, Item>
+where
+    Si: CompletionSink<Item>,
+{
+    pub(super) fn new(sink: Si, capacity: usize) -> Self {
+        Self {
+            sink,
+            buf: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Push as many buffered items into the inner sink as it will currently accept.
+    unsafe fn try_empty_buffer(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Si::Error>> {
+        let mut this = self.project();
+        while !this.buf.is_empty() {
+            core::task::ready!(this.sink.as_mut().poll_ready(cx))?;
+            let item = this.buf.pop_front().expect("buffer known to be non-empty");
+            this.sink.as_mut().start_send(item)?;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<Si, Item> CompletionSink<Item> for Buffer<Si, Item>
+where
+    Si: CompletionSink<Item>,
+{
+    type Error = Si::Error;
+
+    unsafe fn poll_ready(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        if self.capacity == 0 {
+            return self.project().sink.poll_ready(cx);
+        }
+        // Only make room when the buffer is already full; otherwise we can accept immediately.
+        if self.buf.len() >= self.capacity {
+            core::task::ready!(self.as_mut().try_empty_buffer(cx))?;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    unsafe fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error> {
+        let this = self.project();
+        if *this.capacity == 0 {
+            return this.sink.start_send(item);
+        }
+        this.buf.push_back(item);
+        Ok(())
+    }
+
+    unsafe fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        core::task::ready!(self.as_mut().try_empty_buffer(cx))?;
+        debug_assert!(self.buf.is_empty());
+        self.project().sink.poll_flush(cx)
+    }
+
+    unsafe fn poll_close(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        core::task::ready!(self.as_mut().try_empty_buffer(cx))?;
+        debug_assert!(self.buf.is_empty());
+        self.project().sink.poll_close(cx)
+    }
+}
@@ -0,0 +1,329 @@
+//! Utilities for the [`CompletionSink`] trait, a completion-based analogue of [`Sink`].
+//!
+//! [`Sink`]: futures_sink::Sink
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionStream;
+use futures_sink::Sink;
+use pin_project_lite::pin_project;
+
+mod with;
+pub use with::With;
+
+mod buffer;
+#[cfg(feature = "alloc")]
+pub use buffer::Buffer;
+
+mod send;
+pub use send::{Feed, Send, SendAll};
+
+/// A `Sink` whose methods, once polled, must be driven to completion.
+///
+/// This is the sink counterpart to [`CompletionFuture`](completion_core::CompletionFuture) and
+/// [`CompletionStream`]; it mirrors the four-method shape of [`futures_sink::Sink`].
+///
+/// # Safety
+///
+/// Once any of [`poll_ready`](Self::poll_ready), [`poll_flush`](Self::poll_flush) or
+/// [`poll_close`](Self::poll_close) has returned [`Poll::Pending`], the sink must be driven to
+/// completion — it is unsound to drop it without polling the outstanding operation until it
+/// returns [`Poll::Ready`].
+#[must_use = "sinks do nothing unless you use them"]
+pub trait CompletionSink<Item> {
+    /// The type of value produced by the sink when an error occurs.
+    type Error;
+
+    /// Attempt to prepare the sink to receive a value.
+    ///
+    /// # Safety
+    ///
+    /// See the [trait-level documentation](Self).
+    unsafe fn poll_ready(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>>;
+
+    /// Begin the process of sending a value to the sink.
+    ///
+    /// Each call must be preceded by a successful call to [`poll_ready`](Self::poll_ready).
+    ///
+    /// # Safety
+    ///
+    /// See the [trait-level documentation](Self).
+    unsafe fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error>;
+
+    /// Flush any remaining output from this sink.
+    ///
+    /// # Safety
+    ///
+    /// See the [trait-level documentation](Self).
+    unsafe fn poll_flush(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>>;
+
+    /// Flush any remaining output and close this sink, if necessary.
+    ///
+    /// # Safety
+    ///
+    /// See the [trait-level documentation](Self).
+    unsafe fn poll_close(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>>;
+}
+
+impl<S: ?Sized + CompletionSink<Item> + Unpin, Item> CompletionSink<Item> for &mut S {
+    type Error = S::Error;
+
+    unsafe fn poll_ready(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_ready(cx)
+    }
+    unsafe fn start_send(mut self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error> {
+        Pin::new(&mut **self).start_send(item)
+    }
+    unsafe fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_flush(cx)
+    }
+    unsafe fn poll_close(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_close(cx)
+    }
+}
+
+pin_project! {
+    /// Unsafely assert that the inner sink will complete each started operation.
+    ///
+    /// This is the sink analogue of [`AssertCompletes`](crate::AssertCompletes); it lets
+    /// [`Sink`]-only code be driven from a [`CompletionSink`].
+    #[derive(Debug)]
+    #[must_use = "sinks do nothing unless you use them"]
+    pub struct AssertSinkCompletes<T: ?Sized> {
+        #[pin]
+        inner: T,
+    }
+}
+
+impl<T> AssertSinkCompletes<T> {
+    /// Create a new `AssertSinkCompletes` around a sink that will complete each started operation.
+    ///
+    /// # Safety
+    ///
+    /// Once polled, the sink must be driven to completion.
+    pub unsafe fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    /// Take the inner sink.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: ?Sized + CompletionSink<Item>, Item> Sink<Item> for AssertSinkCompletes<T> {
+    type Error = T::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        unsafe { self.project().inner.poll_ready(cx) }
+    }
+    fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error> {
+        unsafe { self.project().inner.start_send(item) }
+    }
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        unsafe { self.project().inner.poll_flush(cx) }
+    }
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        unsafe { self.project().inner.poll_close(cx) }
+    }
+}
+
+impl<T: ?Sized + CompletionSink<Item>, Item> CompletionSink<Item> for AssertSinkCompletes<T> {
+    type Error = T::Error;
+
+    unsafe fn poll_ready(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.project().inner.poll_ready(cx)
+    }
+    unsafe fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error> {
+        self.project().inner.start_send(item)
+    }
+    unsafe fn poll_flush(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.project().inner.poll_flush(cx)
+    }
+    unsafe fn poll_close(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.project().inner.poll_close(cx)
+    }
+}
+
+pin_project! {
+    /// Make sure that a [`Sink`]'s started operations will complete, exposing it as a
+    /// [`CompletionSink`].
+    ///
+    /// This is the sink analogue of [`MustComplete`](crate::MustComplete).
+    #[derive(Debug)]
+    #[must_use = "sinks do nothing unless you use them"]
+    pub struct MustSink<T: ?Sized> {
+        #[pin]
+        inner: T,
+    }
+}
+
+impl<T> MustSink<T> {
+    /// Make sure that the given sink's started operations will complete.
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    /// Take the inner sink.
+    ///
+    /// # Safety
+    ///
+    /// Any started operation on this sink must be driven to completion.
+    pub unsafe fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: ?Sized + Sink<Item>, Item> CompletionSink<Item> for MustSink<T> {
+    type Error = T::Error;
+
+    unsafe fn poll_ready(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.project().inner.poll_ready(cx)
+    }
+    unsafe fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error> {
+        self.project().inner.start_send(item)
+    }
+    unsafe fn poll_flush(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.project().inner.poll_flush(cx)
+    }
+    unsafe fn poll_close(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.project().inner.poll_close(cx)
+    }
+}
+
+/// Extension trait providing combinators for [`CompletionSink`]s.
+pub trait CompletionSinkExt<Item>: CompletionSink<Item> {
+    /// Compose a function in front of the sink, transforming each incoming item via a completion
+    /// future before it is sent.
+    fn with<U, Fut, F>(self, f: F) -> With<Self, Item, U, Fut, F>
+    where
+        Self: Sized,
+        F: FnMut(U) -> Fut,
+        Fut: completion_core::CompletionFuture<Output = Result<Item, Self::Error>>,
+    {
+        With::new(self, f)
+    }
+
+    /// Buffer up to `n` items before sending them onwards to the underlying sink.
+    #[cfg(feature = "alloc")]
+    fn buffer(self, n: usize) -> Buffer<Self, Item>
+    where
+        Self: Sized,
+    {
+        Buffer::new(self, n)
+    }
+
+    /// A completion future that completes after sending an item into the sink, flushing it.
+    fn send(&mut self, item: Item) -> Send<'_, Self, Item>
+    where
+        Self: Unpin,
+    {
+        Send::new(self, item)
+    }
+
+    /// A completion future that completes after feeding an item into the sink, without flushing.
+    fn feed(&mut self, item: Item) -> Feed<'_, Self, Item>
+    where
+        Self: Unpin,
+    {
+        Feed::new(self, item)
+    }
+
+    /// A completion future that completes after sending every item of a [`CompletionStream`] into
+    /// the sink, flushing it.
+    fn send_all<'a, St>(&'a mut self, stream: &'a mut St) -> SendAll<'a, Self, St>
+    where
+        Self: Unpin,
+        St: CompletionStream<Item = Item> + Unpin + ?Sized,
+    {
+        SendAll::new(self, stream)
+    }
+}
+
+impl<Item, S: ?Sized + CompletionSink<Item>> CompletionSinkExt<Item> for S {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+    use std::vec::Vec;
+
+    use crate::future::block_on;
+
+    /// A minimal completion sink that collects sent items into a `Vec`.
+    struct VecSink {
+        items: Vec<i32>,
+    }
+
+    impl CompletionSink<i32> for VecSink {
+        type Error = Infallible;
+
+        unsafe fn poll_ready(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        unsafe fn start_send(self: Pin<&mut Self>, item: i32) -> Result<(), Self::Error> {
+            self.get_mut().items.push(item);
+            Ok(())
+        }
+        unsafe fn poll_flush(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        unsafe fn poll_close(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[test]
+    fn send_delivers_items_in_order() {
+        let mut sink = VecSink { items: Vec::new() };
+        block_on(sink.send(1)).unwrap();
+        block_on(sink.send(2)).unwrap();
+        assert_eq!(sink.items, [1, 2]);
+    }
+}
@@ -0,0 +1,136 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::{CompletionFuture, CompletionStream};
+
+use super::CompletionSink;
+
+/// A completion future that feeds an item into a sink without flushing it.
+///
+/// This is created by [`CompletionSinkExt::feed`](super::CompletionSinkExt::feed).
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you use them"]
+pub struct Feed<'a, Si: ?Sized, Item> {
+    sink: &'a mut Si,
+    item: Option<Item>,
+}
+
+impl<'a, Si: ?Sized + CompletionSink<Item> + Unpin, Item> Feed<'a, Si, Item> {
+    pub(super) fn new(sink: &'a mut Si, item: Item) -> Self {
+        Self {
+            sink,
+            item: Some(item),
+        }
+    }
+}
+
+impl<Si: ?Sized + CompletionSink<Item> + Unpin, Item> CompletionFuture for Feed<'_, Si, Item> {
+    type Output = Result<(), Si::Error>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut sink = Pin::new(&mut *this.sink);
+
+        if let Err(e) = core::task::ready!(sink.as_mut().poll_ready(cx)) {
+            return Poll::Ready(Err(e));
+        }
+        let item = this.item.take().expect("`Feed` polled after completion");
+        Poll::Ready(sink.start_send(item))
+    }
+}
+
+/// A completion future that sends an item into a sink, flushing it.
+///
+/// This is created by [`CompletionSinkExt::send`](super::CompletionSinkExt::send).
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you use them"]
+pub struct Send<'a, Si: ?Sized, Item> {
+    feed: Feed<'a, Si, Item>,
+}
+
+impl<'a, Si: ?Sized + CompletionSink<Item> + Unpin, Item> Send<'a, Si, Item> {
+    pub(super) fn new(sink: &'a mut Si, item: Item) -> Self {
+        Self {
+            feed: Feed::new(sink, item),
+        }
+    }
+}
+
+impl<Si: ?Sized + CompletionSink<Item> + Unpin, Item> CompletionFuture for Send<'_, Si, Item> {
+    type Output = Result<(), Si::Error>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if this.feed.item.is_some() {
+            if let Err(e) = core::task::ready!(Pin::new(&mut this.feed).poll(cx)) {
+                return Poll::Ready(Err(e));
+            }
+        }
+        Pin::new(&mut *this.feed.sink).poll_flush(cx)
+    }
+}
+
+/// A completion future that sends every item of a stream into a sink, flushing it.
+///
+/// This is created by [`CompletionSinkExt::send_all`](super::CompletionSinkExt::send_all).
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you use them"]
+pub struct SendAll<'a, Si: ?Sized, St: ?Sized> {
+    sink: &'a mut Si,
+    stream: &'a mut St,
+    buffered: Option<St::Item>,
+}
+
+impl<'a, Si, St> SendAll<'a, Si, St>
+where
+    Si: ?Sized + CompletionSink<St::Item> + Unpin,
+    St: ?Sized + CompletionStream + Unpin,
+{
+    pub(super) fn new(sink: &'a mut Si, stream: &'a mut St) -> Self {
+        Self {
+            sink,
+            stream,
+            buffered: None,
+        }
+    }
+}
+
+impl<Si, St> CompletionFuture for SendAll<'_, Si, St>
+where
+    Si: ?Sized + CompletionSink<St::Item> + Unpin,
+    St: ?Sized + CompletionStream + Unpin,
+{
+    type Output = Result<(), Si::Error>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(item) = this.buffered.take() {
+                let mut sink = Pin::new(&mut *this.sink);
+                match sink.as_mut().poll_ready(cx) {
+                    Poll::Ready(Ok(())) => {
+                        if let Err(e) = sink.start_send(item) {
+                            return Poll::Ready(Err(e));
+                        }
+                    }
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                    Poll::Pending => {
+                        this.buffered = Some(item);
+                        return Poll::Pending;
+                    }
+                }
+            }
+
+            match Pin::new(&mut *this.stream).poll_next(cx) {
+                Poll::Ready(Some(item)) => this.buffered = Some(item),
+                Poll::Ready(None) => return Pin::new(&mut *this.sink).poll_flush(cx),
+                Poll::Pending => {
+                    core::task::ready!(Pin::new(&mut *this.sink).poll_flush(cx))?;
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
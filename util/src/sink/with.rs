@@ -0,0 +1,130 @@
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+use futures_sink::Sink;
+use pin_project_lite::pin_project;
+
+use super::CompletionSink;
+
+pin_project! {
+    /// Sink adapter that transforms each incoming item through a completion future before sending
+    /// it onwards.
+    ///
+    /// This is created by [`CompletionSinkExt::with`](super::CompletionSinkExt::with).
+    #[must_use = "sinks do nothing unless you use them"]
+    pub struct With<Si, Item, U, Fut, F> {
+        #[pin]
+        sink: Si,
+        f: F,
+        #[pin]
+        state: Option<Fut>,
+        _marker: PhantomData<fn(U) -> Item>,
+    }
+}
+
+impl<Si, Item, U, Fut, F> With<Si, Item, U, Fut, F> {
+    pub(super) fn new(sink: Si, f: F) -> Self {
+        Self {
+            sink,
+            f,
+            state: None,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<Si, Item, U, Fut, F> With<Si, Item, U, Fut, F>
+where
+    Si: CompletionSink<Item>,
+    F: FnMut(U) -> Fut,
+    Fut: CompletionFuture<Output = Result<Item, Si::Error>>,
+{
+    /// Drive any pending mapping future so that the item it produces is handed to the inner sink.
+    unsafe fn poll_pending(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Si::Error>> {
+        let mut this = self.project();
+        if this.state.is_none() {
+            return Poll::Ready(Ok(()));
+        }
+        // Wait for the inner sink first, so a produced item never has to wait for room.
+        core::task::ready!(this.sink.as_mut().poll_ready(cx))?;
+        let fut = this.state.as_mut().as_pin_mut().expect("state checked above");
+        let item = match core::task::ready!(fut.poll(cx)) {
+            Ok(item) => item,
+            Err(e) => {
+                this.state.set(None);
+                return Poll::Ready(Err(e));
+            }
+        };
+        this.state.set(None);
+        Poll::Ready(this.sink.start_send(item))
+    }
+}
+
+impl<Si, Item, U, Fut, F> CompletionSink<U> for With<Si, Item, U, Fut, F>
+where
+    Si: CompletionSink<Item>,
+    F: FnMut(U) -> Fut,
+    Fut: CompletionFuture<Output = Result<Item, Si::Error>>,
+{
+    type Error = Si::Error;
+
+    unsafe fn poll_ready(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        core::task::ready!(self.as_mut().poll_pending(cx))?;
+        self.project().sink.poll_ready(cx)
+    }
+
+    unsafe fn start_send(self: Pin<&mut Self>, item: U) -> Result<(), Self::Error> {
+        let mut this = self.project();
+        let fut = (this.f)(item);
+        this.state.set(Some(fut));
+        Ok(())
+    }
+
+    unsafe fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        core::task::ready!(self.as_mut().poll_pending(cx))?;
+        self.project().sink.poll_flush(cx)
+    }
+
+    unsafe fn poll_close(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        core::task::ready!(self.as_mut().poll_pending(cx))?;
+        self.project().sink.poll_close(cx)
+    }
+}
+
+// Also usable as a plain `Sink` when the mapping future and inner sink both complete eagerly.
+impl<Si, Item, U, Fut, F> Sink<U> for With<Si, Item, U, Fut, F>
+where
+    Si: CompletionSink<Item> + Sink<Item, Error = <Si as CompletionSink<Item>>::Error>,
+    F: FnMut(U) -> Fut,
+    Fut: CompletionFuture<Output = Result<Item, <Si as CompletionSink<Item>>::Error>>
+        + core::future::Future<Output = Result<Item, <Si as CompletionSink<Item>>::Error>>,
+{
+    type Error = <Si as CompletionSink<Item>>::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        unsafe { CompletionSink::poll_ready(self, cx) }
+    }
+    fn start_send(self: Pin<&mut Self>, item: U) -> Result<(), Self::Error> {
+        unsafe { CompletionSink::start_send(self, item) }
+    }
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        unsafe { CompletionSink::poll_flush(self, cx) }
+    }
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        unsafe { CompletionSink::poll_close(self, cx) }
+    }
+}
@@ -0,0 +1,73 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::{CompletionFuture, CompletionStream};
+use pin_project_lite::pin_project;
+
+use super::FuturesUnordered;
+
+pin_project! {
+    /// Stream adapter that runs a bounded number of completion futures concurrently.
+    ///
+    /// This is created by the
+    /// [`buffer_unordered`](crate::CompletionStreamExt::buffer_unordered) method.
+    ///
+    /// The source stream yields completion futures; up to `n` of them are polled concurrently via
+    /// a [`FuturesUnordered`], and their outputs are produced in completion order.
+    #[must_use = "streams do nothing unless you use them"]
+    pub struct BufferUnordered<St>
+    where
+        St: CompletionStream,
+        St::Item: CompletionFuture,
+    {
+        #[pin]
+        stream: St,
+        in_progress: FuturesUnordered<St::Item>,
+        max: usize,
+        stream_done: bool,
+    }
+}
+
+impl<St> BufferUnordered<St>
+where
+    St: CompletionStream,
+    St::Item: CompletionFuture,
+{
+    pub(crate) fn new(stream: St, n: usize) -> Self {
+        assert!(n > 0, "`buffer_unordered` requires a buffer size of at least 1");
+        Self {
+            stream,
+            in_progress: FuturesUnordered::new(),
+            max: n,
+            stream_done: false,
+        }
+    }
+}
+
+impl<St> CompletionStream for BufferUnordered<St>
+where
+    St: CompletionStream,
+    St::Item: CompletionFuture,
+{
+    type Item = <St::Item as CompletionFuture>::Output;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        // Pull futures from the source stream until the buffer is full.
+        while !*this.stream_done && this.in_progress.len() < *this.max {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(fut)) => this.in_progress.push(fut),
+                Poll::Ready(None) => *this.stream_done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        match Pin::new(&mut *this.in_progress).poll_next(cx) {
+            Poll::Ready(Some(output)) => Poll::Ready(Some(output)),
+            // The set is exhausted; we are done only once the source stream is too.
+            Poll::Ready(None) if *this.stream_done => Poll::Ready(None),
+            _ => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use core::any::Any;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::boxed::Box;
+use std::panic::{catch_unwind, AssertUnwindSafe, UnwindSafe};
+
+use completion_core::CompletionStream;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// A completion stream that catches panics from the inner stream while it is being polled.
+    ///
+    /// This is created by the
+    /// [`catch_unwind`](crate::CompletionStreamExt::catch_unwind) method.
+    ///
+    /// The first caught panic is yielded as an [`Err`] item and terminates the stream; the inner
+    /// stream is never polled again.
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless you use them"]
+    pub struct CatchUnwind<S> {
+        #[pin]
+        inner: S,
+        done: bool,
+    }
+}
+
+impl<S> CatchUnwind<S> {
+    pub(crate) fn new(inner: S) -> Self {
+        Self { inner, done: false }
+    }
+}
+
+impl<S: CompletionStream + UnwindSafe> CompletionStream for CatchUnwind<S> {
+    type Item = Result<S::Item, Box<dyn Any + Send>>;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        match catch_unwind(AssertUnwindSafe(|| unsafe { this.inner.poll_next(cx) })) {
+            Ok(Poll::Ready(Some(item))) => Poll::Ready(Some(Ok(item))),
+            Ok(Poll::Ready(None)) => {
+                *this.done = true;
+                Poll::Ready(None)
+            }
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => {
+                *this.done = true;
+                Poll::Ready(Some(Err(payload)))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            let (_, upper) = self.inner.size_hint();
+            (0, upper.map(|upper| upper.saturating_add(1)))
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use std::sync::Mutex;
+use std::task::{RawWaker, RawWakerVTable, Waker};
+
+use completion_core::{CompletionFuture, CompletionStream};
+
+use crate::future::block_on::wake_pair;
+
+/// A set of completion futures driven concurrently, yielding each output as it completes.
+///
+/// This is the completion-based counterpart to [`futures_util::stream::FuturesUnordered`]. Each
+/// contained future is polled only when its own waker fires; a shared ready-queue records which
+/// tasks need polling.
+///
+/// Because every contained future must be polled to completion, dropping a `FuturesUnordered`
+/// drives each remaining future to completion rather than dropping it mid-flight, and pushing a
+/// future after cancellation has begun is rejected.
+///
+/// [`futures_util::stream::FuturesUnordered`]: https://docs.rs/futures-util/latest/futures_util/stream/struct.FuturesUnordered.html
+pub struct FuturesUnordered<F> {
+    tasks: Vec<Option<Pin<Box<F>>>>,
+    /// Indices of `tasks` slots emptied by completed futures, available for reuse.
+    free: Vec<usize>,
+    /// Number of occupied slots, tracked so `len`/`is_empty` stay O(1).
+    len: usize,
+    shared: Arc<Shared>,
+    cancelling: bool,
+}
+
+struct Shared {
+    ready: Mutex<VecDeque<usize>>,
+    parent: Mutex<Option<Waker>>,
+}
+
+impl<F> FuturesUnordered<F> {
+    /// Create a new, empty `FuturesUnordered`.
+    pub fn new() -> Self {
+        Self {
+            tasks: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+            shared: Arc::new(Shared {
+                ready: Mutex::new(VecDeque::new()),
+                parent: Mutex::new(None),
+            }),
+            cancelling: false,
+        }
+    }
+
+    /// Push a completion future into the set.
+    ///
+    /// The future will start being polled on the next poll of the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the set has begun cancelling its futures (see the type-level docs); inserting a
+    /// new future then could not honor the poll-to-completion contract.
+    pub fn push(&mut self, future: F) {
+        assert!(
+            !self.cancelling,
+            "cannot push onto a `FuturesUnordered` that is being cancelled"
+        );
+        let idx = match self.free.pop() {
+            Some(idx) => {
+                self.tasks[idx] = Some(Box::pin(future));
+                idx
+            }
+            None => {
+                self.tasks.push(Some(Box::pin(future)));
+                self.tasks.len() - 1
+            }
+        };
+        self.len += 1;
+        self.shared.ready.lock().unwrap().push_back(idx);
+    }
+
+    /// The number of futures currently in the set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the set contains no futures.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return an iterator over pinned mutable references to the futures in the set.
+    pub fn iter_pin_mut(self: Pin<&mut Self>) -> IterPinMut<'_, F> {
+        // SAFETY: we only hand out pinned references and never move the futures.
+        let tasks = unsafe { &mut self.get_unchecked_mut().tasks };
+        IterPinMut {
+            inner: tasks.iter_mut(),
+        }
+    }
+
+    fn task_waker(&self, idx: usize) -> Waker {
+        let data = Arc::new(TaskWaker {
+            idx,
+            shared: Arc::clone(&self.shared),
+        });
+        unsafe { Waker::from_raw(raw_waker(data)) }
+    }
+}
+
+impl<F> Default for FuturesUnordered<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: CompletionFuture> CompletionStream for FuturesUnordered<F> {
+    type Item = F::Output;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_unchecked_mut();
+
+        *this.shared.parent.lock().unwrap() = Some(cx.waker().clone());
+
+        loop {
+            let idx = match this.shared.ready.lock().unwrap().pop_front() {
+                Some(idx) => idx,
+                None => {
+                    return if this.is_empty() {
+                        Poll::Ready(None)
+                    } else {
+                        Poll::Pending
+                    };
+                }
+            };
+
+            let fut = match this.tasks.get_mut(idx).and_then(Option::as_mut) {
+                Some(fut) => fut,
+                None => continue,
+            };
+
+            let waker = this.task_waker(idx);
+            let mut cx = Context::from_waker(&waker);
+            if let Poll::Ready(output) = fut.as_mut().poll(&mut cx) {
+                this.tasks[idx] = None;
+                this.free.push(idx);
+                this.len -= 1;
+                return Poll::Ready(Some(output));
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<F: CompletionFuture> Drop for FuturesUnordered<F> {
+    fn drop(&mut self) {
+        // Every contained future has potentially been polled, so we must drive each to completion
+        // rather than dropping it mid-flight.
+        self.cancelling = true;
+        for slot in &mut self.tasks {
+            if let Some(mut fut) = slot.take() {
+                // Drive the future to completion with a `block_on`-style park loop rather than
+                // dropping it mid-flight.
+                let (parker, waker) = wake_pair();
+                let mut cx = Context::from_waker(&waker);
+                loop {
+                    if unsafe { fut.as_mut().poll(&mut cx) }.is_ready() {
+                        break;
+                    }
+                    parker.park();
+                }
+            }
+        }
+    }
+}
+
+impl<F> fmt::Debug for FuturesUnordered<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FuturesUnordered")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Iterator over pinned mutable references to the futures of a [`FuturesUnordered`], created by
+/// [`FuturesUnordered::iter_pin_mut`].
+#[derive(Debug)]
+pub struct IterPinMut<'a, F> {
+    inner: core::slice::IterMut<'a, Option<Pin<Box<F>>>>,
+}
+
+impl<'a, F> Iterator for IterPinMut<'a, F> {
+    type Item = Pin<&'a mut F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.inner.next() {
+                Some(Some(fut)) => return Some(fut.as_mut()),
+                Some(None) => continue,
+                None => return None,
+            }
+        }
+    }
+}
+
+struct TaskWaker {
+    idx: usize,
+    shared: Arc<Shared>,
+}
+
+impl TaskWaker {
+    fn wake(&self) {
+        self.shared.ready.lock().unwrap().push_back(self.idx);
+        if let Some(waker) = &*self.shared.parent.lock().unwrap() {
+            waker.wake_by_ref();
+        }
+    }
+}
+
+fn raw_waker(data: Arc<TaskWaker>) -> RawWaker {
+    RawWaker::new(Arc::into_raw(data) as *const (), &VTABLE)
+}
+
+unsafe fn clone(ptr: *const ()) -> RawWaker {
+    let data = Arc::from_raw(ptr as *const TaskWaker);
+    let cloned = Arc::clone(&data);
+    core::mem::forget(data);
+    raw_waker(cloned)
+}
+unsafe fn wake(ptr: *const ()) {
+    let data = Arc::from_raw(ptr as *const TaskWaker);
+    data.wake();
+}
+unsafe fn wake_by_ref(ptr: *const ()) {
+    let data = Arc::from_raw(ptr as *const TaskWaker);
+    data.wake();
+    core::mem::forget(data);
+}
+unsafe fn drop(ptr: *const ()) {
+    Arc::from_raw(ptr as *const TaskWaker);
+}
+
+const VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::sync::Arc;
+    use alloc::vec::Vec;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::future::block_on;
+    use crate::MustComplete;
+
+    /// Drain a completion stream into a `Vec`, in completion order.
+    fn drain<S: CompletionStream + Unpin>(stream: S) -> Vec<S::Item> {
+        struct Drain<S: CompletionStream> {
+            stream: S,
+            out: Vec<S::Item>,
+        }
+        impl<S: CompletionStream + Unpin> CompletionFuture for Drain<S> {
+            type Output = Vec<S::Item>;
+            unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = self.get_mut();
+                loop {
+                    match Pin::new(&mut this.stream).poll_next(cx) {
+                        Poll::Ready(Some(item)) => this.out.push(item),
+                        Poll::Ready(None) => return Poll::Ready(core::mem::take(&mut this.out)),
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+            }
+        }
+        block_on(Drain {
+            stream,
+            out: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn yields_every_output() {
+        let mut set = FuturesUnordered::new();
+        set.push(MustComplete::new(core::future::ready(1)));
+        set.push(MustComplete::new(core::future::ready(2)));
+        assert_eq!(set.len(), 2);
+        assert!(!set.is_empty());
+
+        let mut out = drain(set);
+        out.sort_unstable();
+        assert_eq!(out, [1, 2]);
+    }
+
+    /// A future that completes only on its second poll, recording its completion.
+    struct Counter {
+        polled: bool,
+        done: Arc<AtomicUsize>,
+    }
+    impl CompletionFuture for Counter {
+        type Output = ();
+        unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            let this = self.get_unchecked_mut();
+            if this.polled {
+                this.done.fetch_add(1, Ordering::SeqCst);
+                Poll::Ready(())
+            } else {
+                this.polled = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn drives_remaining_futures_on_drop() {
+        let done = Arc::new(AtomicUsize::new(0));
+        let mut set = FuturesUnordered::new();
+        set.push(Counter {
+            polled: false,
+            done: Arc::clone(&done),
+        });
+        set.push(Counter {
+            polled: false,
+            done: Arc::clone(&done),
+        });
+        // Drop without ever draining: both futures must still be driven to completion.
+        drop(set);
+        assert_eq!(done.load(Ordering::SeqCst), 2);
+    }
+}
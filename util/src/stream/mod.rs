@@ -0,0 +1,74 @@
+//! Utilities for the [`CompletionStream`] trait.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use core::pin::Pin;
+
+#[cfg(feature = "std")]
+use completion_core::CompletionFuture;
+use completion_core::CompletionStream;
+use futures_core::Stream;
+
+use crate::MustComplete;
+
+#[cfg(feature = "std")]
+mod futures_unordered;
+#[cfg(feature = "std")]
+pub use futures_unordered::{FuturesUnordered, IterPinMut};
+
+#[cfg(feature = "std")]
+mod buffer_unordered;
+#[cfg(feature = "std")]
+pub use buffer_unordered::BufferUnordered;
+
+#[cfg(feature = "std")]
+mod catch_unwind;
+#[cfg(feature = "std")]
+pub use catch_unwind::CatchUnwind;
+
+/// A boxed [`CompletionStream`] that is [`Send`].
+#[cfg(feature = "alloc")]
+pub type BoxCompletionStream<'a, T> = Pin<Box<dyn CompletionStream<Item = T> + Send + 'a>>;
+
+/// A boxed [`CompletionStream`] that is not necessarily [`Send`].
+#[cfg(feature = "alloc")]
+pub type LocalBoxCompletionStream<'a, T> = Pin<Box<dyn CompletionStream<Item = T> + 'a>>;
+
+/// Extension trait for [`Stream`]s, providing conversions into [`CompletionStream`]s.
+pub trait StreamExt: Stream {
+    /// Make sure that this stream will complete, yielding a [`CompletionStream`].
+    fn must_complete(self) -> MustComplete<Self>
+    where
+        Self: Sized,
+    {
+        MustComplete::new(self)
+    }
+}
+impl<S: Stream + ?Sized> StreamExt for S {}
+
+/// Extension trait for [`CompletionStream`]s, providing combinators.
+pub trait CompletionStreamExt: CompletionStream {
+    /// Run up to `n` of this stream's completion futures concurrently, yielding their outputs in
+    /// completion order.
+    #[cfg(feature = "std")]
+    fn buffer_unordered(self, n: usize) -> BufferUnordered<Self>
+    where
+        Self: Sized,
+        Self::Item: CompletionFuture,
+    {
+        BufferUnordered::new(self, n)
+    }
+
+    /// Catch panics unwinding out of this stream while it is being polled.
+    ///
+    /// The first caught panic is yielded as an [`Err`] item and terminates the stream.
+    #[cfg(feature = "std")]
+    fn catch_unwind(self) -> CatchUnwind<Self>
+    where
+        Self: Sized + std::panic::UnwindSafe,
+    {
+        CatchUnwind::new(self)
+    }
+}
+impl<S: CompletionStream + ?Sized> CompletionStreamExt for S {}